@@ -1,36 +1,431 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::stdout;
+use std::panic;
+use std::time::{Duration, Instant};
+
+use argh::FromArgs;
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{
+    cursor::Show,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers, MouseEvent, MouseEventKind,
+    },
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
+    backend::CrosstermBackend,
+    layout::{Constraint, Layout, Position, Rect},
     style::{palette::tailwind::SLATE, Stylize},
     symbols,
     text::Line,
     widgets::{Block, Borders},
-    DefaultTerminal, Frame,
+    DefaultTerminal, Frame, Terminal,
 };
-use tui_bar_graph::BarGraph;
+use sysinfo::{Disks, System};
+use tui_bar_graph::{BarGraph, BarStyle};
+
+/// Puts the terminal into raw mode and the alternate screen, and installs a panic hook that
+/// restores it before handing off to whatever hook was previously installed. The hook stays
+/// installed after [`restore`]; a panic before this `init` would still leave the terminal in a
+/// bad state, so call this as early as possible in `main`.
+///
+/// Panics if the terminal can't be initialized; use [`try_init`] to handle that case instead.
+pub fn init() -> DefaultTerminal {
+    try_init().expect("failed to initialize the terminal")
+}
+
+/// Fallible variant of [`init`] for callers who want to handle setup failures themselves (e.g. to
+/// print a friendlier message) instead of letting `init` panic.
+pub fn try_init() -> Result<DefaultTerminal> {
+    install_panic_hook();
+    enter_terminal()
+}
+
+/// Restores the terminal to its original state: raw mode off, alternate screen left, cursor
+/// shown, mouse capture disabled. Errors are ignored since this is usually called on the way out
+/// of `main`, when there's nothing useful left to do with them; use [`try_restore`] to observe
+/// them instead.
+pub fn restore() {
+    let _ = try_restore();
+}
+
+/// Fallible variant of [`restore`], used internally by the panic hook since panicking again while
+/// already panicking would abort the process instead of unwinding.
+pub fn try_restore() -> Result<()> {
+    disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+    Ok(())
+}
+
+fn enter_terminal() -> Result<DefaultTerminal> {
+    enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    Ok(Terminal::new(CrosstermBackend::new(stdout()))?)
+}
+
+/// Installs a panic hook that restores the terminal before chaining to the previously installed
+/// hook, so a panic mid-draw never leaves the user's shell in raw mode / the alternate screen.
+fn install_panic_hook() {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        let _ = try_restore();
+        previous_hook(panic_info);
+    }));
+}
+
+/// Number of samples retained per metric history. A graph wider than this many samples is given
+/// only the retained ones.
+const HISTORY_LEN: usize = 256;
+
+/// A terminal dashboard of system metrics.
+#[derive(Debug, FromArgs)]
+pub struct Cli {
+    /// time in milliseconds between ticks
+    #[argh(option, default = "250")]
+    pub tick_rate: u64,
+
+    /// use high-resolution braille bars instead of coarse block bars
+    #[argh(switch)]
+    pub enhanced_graphics: bool,
+
+    /// gradient palette for the bar graphs (plasma, blues or viridis; other names use plasma)
+    #[argh(option, default = "String::from(\"plasma\")")]
+    pub gradient: String,
+
+    /// metric source to sample from (random, live)
+    #[argh(option, default = "String::from(\"random\")")]
+    pub metrics: String,
+}
+
+/// The gradient palette applied to every [`BarGraph`], selected via [`Cli::gradient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gradient {
+    Plasma,
+    Blues,
+    Viridis,
+}
+
+impl Gradient {
+    /// Parses a `--gradient` name, falling back to [`Gradient::Plasma`] for anything unknown.
+    fn from_name(name: &str) -> Self {
+        match name {
+            "blues" => Self::Blues,
+            "viridis" => Self::Viridis,
+            _ => Self::Plasma,
+        }
+    }
+
+    fn resolve(self) -> colorgrad::BasisGradient {
+        match self {
+            Self::Plasma => colorgrad::preset::plasma(),
+            Self::Blues => colorgrad::preset::blues(),
+            Self::Viridis => colorgrad::preset::viridis(),
+        }
+    }
+}
+
+/// Selects which [`MetricSource`] implementation backs each panel, via `--metrics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsProvider {
+    /// Uniformly random samples, matching the dashboard's original placeholder behaviour.
+    Random,
+    /// Live CPU/disk/memory utilization read from the host via `sysinfo`.
+    Live,
+}
+
+impl MetricsProvider {
+    /// Parses a `--metrics` name, falling back to [`MetricsProvider::Random`] for anything
+    /// unknown.
+    fn from_name(name: &str) -> Self {
+        match name {
+            "live" => Self::Live,
+            _ => Self::Random,
+        }
+    }
+}
+
+/// Runtime configuration derived from [`Cli`] and threaded through [`App`].
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    pub tick_rate: Duration,
+    pub enhanced_graphics: bool,
+    pub gradient: Gradient,
+    pub metrics: MetricsProvider,
+}
+
+impl From<Cli> for Config {
+    fn from(cli: Cli) -> Self {
+        Self {
+            tick_rate: Duration::from_millis(cli.tick_rate),
+            enhanced_graphics: cli.enhanced_graphics,
+            gradient: Gradient::from_name(&cli.gradient),
+            metrics: MetricsProvider::from_name(&cli.metrics),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::from(Cli {
+            tick_rate: 250,
+            enhanced_graphics: false,
+            gradient: String::from("plasma"),
+            metrics: String::from("random"),
+        })
+    }
+}
+
+/// A source of samples for one metric panel, in the `0.0..=1.0` range [`BarGraph`] expects.
+trait MetricSource: fmt::Debug {
+    /// Reads the next sample from the source.
+    fn sample(&mut self) -> f64;
+
+    /// A short, human-readable name for the metric this source reports on.
+    fn label(&self) -> &str;
+}
+
+/// Produces uniformly random samples, matching the dashboard's original placeholder behaviour.
+#[derive(Debug)]
+struct RandomSource {
+    label: &'static str,
+}
+
+impl RandomSource {
+    fn new(label: &'static str) -> Self {
+        Self { label }
+    }
+}
+
+impl MetricSource for RandomSource {
+    fn sample(&mut self) -> f64 {
+        rand::random::<f64>()
+    }
+
+    fn label(&self) -> &str {
+        self.label
+    }
+}
+
+/// Which host metric a [`SysinfoSource`] reports on.
+#[derive(Debug, Clone, Copy)]
+enum SysinfoMetric {
+    Cpu,
+    Disk,
+    Memory,
+}
 
-#[derive(Debug, Default)]
+/// Reads live utilization from the host via `sysinfo`. There's no GPU counterpart here since
+/// `sysinfo` doesn't expose GPU utilization, so the GPU panel keeps using [`RandomSource`] even
+/// when [`MetricsProvider::Live`] is selected.
+#[derive(Debug)]
+struct SysinfoSource {
+    system: System,
+    disks: Disks,
+    metric: SysinfoMetric,
+    /// When the CPU usage was last refreshed, so `sample` can honor
+    /// [`sysinfo::MINIMUM_CPU_UPDATE_INTERVAL`] by reusing `last_cpu_usage` instead of blocking
+    /// the calling thread until a fresh reading is due.
+    last_cpu_refresh: Instant,
+    /// The most recently computed CPU usage, returned as-is when called again before
+    /// [`sysinfo::MINIMUM_CPU_UPDATE_INTERVAL`] has elapsed.
+    last_cpu_usage: f64,
+}
+
+impl SysinfoSource {
+    fn new(metric: SysinfoMetric) -> Self {
+        let mut system = System::new();
+        system.refresh_cpu_usage();
+        system.refresh_memory();
+        let mut disks = Disks::new();
+        disks.refresh(true);
+        let last_cpu_usage = system.global_cpu_usage() as f64 / 100.0;
+        Self {
+            system,
+            disks,
+            metric,
+            last_cpu_refresh: Instant::now(),
+            last_cpu_usage,
+        }
+    }
+}
+
+impl MetricSource for SysinfoSource {
+    fn sample(&mut self) -> f64 {
+        match self.metric {
+            SysinfoMetric::Cpu => {
+                // `sysinfo` only produces an accurate reading once MINIMUM_CPU_UPDATE_INTERVAL has
+                // passed since the last refresh, and sleeping until then would block the UI
+                // thread, so reuse the last reading until the interval has elapsed.
+                if self.last_cpu_refresh.elapsed() >= sysinfo::MINIMUM_CPU_UPDATE_INTERVAL {
+                    self.system.refresh_cpu_usage();
+                    self.last_cpu_refresh = Instant::now();
+                    self.last_cpu_usage = self.system.global_cpu_usage() as f64 / 100.0;
+                }
+                self.last_cpu_usage
+            }
+            SysinfoMetric::Memory => {
+                self.system.refresh_memory();
+                let total = self.system.total_memory().max(1);
+                self.system.used_memory() as f64 / total as f64
+            }
+            SysinfoMetric::Disk => {
+                // Aggregate usage across every mounted disk rather than just the first one, so a
+                // nearly-full secondary mount isn't hidden by a mostly-empty boot disk (or vice
+                // versa).
+                self.disks.refresh(true);
+                let (used, total) = self.disks.list().iter().fold(
+                    (0u64, 0u64),
+                    |(used, total), disk| {
+                        let disk_total = disk.total_space();
+                        let disk_used = disk_total.saturating_sub(disk.available_space());
+                        (used + disk_used, total + disk_total)
+                    },
+                );
+                if total == 0 {
+                    0.0
+                } else {
+                    used as f64 / total as f64
+                }
+            }
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self.metric {
+            SysinfoMetric::Cpu => "CPU",
+            SysinfoMetric::Disk => "Disk",
+            SysinfoMetric::Memory => "Memory",
+        }
+    }
+}
+
+/// Builds the [`MetricSource`] configured for `metric`, falling back to [`RandomSource`] when
+/// `provider` is [`MetricsProvider::Random`].
+fn metric_source(
+    provider: MetricsProvider,
+    metric: SysinfoMetric,
+    label: &'static str,
+) -> Box<dyn MetricSource> {
+    match provider {
+        MetricsProvider::Random => Box::new(RandomSource::new(label)),
+        MetricsProvider::Live => Box::new(SysinfoSource::new(metric)),
+    }
+}
+
+/// Identifies one of the dashboard's panels, used to report which one the mouse is over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PanelId {
+    Cpu,
+    Gpu,
+    Disk,
+    Memory,
+}
+
+impl PanelId {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Gpu => "GPU",
+            Self::Disk => "Disk",
+            Self::Memory => "Memory",
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct App {
     /// Is the application running?
     exit: bool,
+    /// User-configurable settings parsed from the command line.
+    config: Config,
+    /// Rolling sample history for the CPU panel.
+    cpu_history: VecDeque<f64>,
+    /// Rolling sample history for the GPU panel.
+    gpu_history: VecDeque<f64>,
+    /// Rolling sample history for the Disk panel.
+    disk_history: VecDeque<f64>,
+    /// Rolling sample history for the Memory panel.
+    memory_history: VecDeque<f64>,
+    /// Area the CPU graph was last drawn into, used to map mouse positions back to samples.
+    cpu_area: Rect,
+    /// Area the GPU graph was last drawn into.
+    gpu_area: Rect,
+    /// Area the Disk panel was last drawn into.
+    disk_area: Rect,
+    /// Area the Memory graph was last drawn into.
+    memory_area: Rect,
+    /// The panel and sample index currently under the mouse cursor, if any.
+    hovered: Option<(PanelId, usize)>,
+    /// Where each panel's samples come from, selected via [`Config::metrics`].
+    cpu_source: Box<dyn MetricSource>,
+    gpu_source: Box<dyn MetricSource>,
+    disk_source: Box<dyn MetricSource>,
+    memory_source: Box<dyn MetricSource>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
 }
 
 impl App {
-    /// Construct a new instance of [`App`].
-    pub fn new() -> Self {
-        Self::default()
+    /// Construct a new instance of [`App`] from parsed command-line [`Config`].
+    pub fn new(config: Config) -> Self {
+        Self {
+            exit: false,
+            cpu_history: VecDeque::with_capacity(HISTORY_LEN),
+            gpu_history: VecDeque::with_capacity(HISTORY_LEN),
+            disk_history: VecDeque::with_capacity(HISTORY_LEN),
+            memory_history: VecDeque::with_capacity(HISTORY_LEN),
+            cpu_area: Rect::default(),
+            gpu_area: Rect::default(),
+            disk_area: Rect::default(),
+            memory_area: Rect::default(),
+            hovered: None,
+            cpu_source: metric_source(config.metrics, SysinfoMetric::Cpu, "CPU"),
+            gpu_source: Box::new(RandomSource::new("GPU")),
+            disk_source: metric_source(config.metrics, SysinfoMetric::Disk, "Disk"),
+            memory_source: metric_source(config.metrics, SysinfoMetric::Memory, "Memory"),
+            config,
+        }
     }
 
     /// Run the application's main loop.
+    ///
+    /// This follows the classic tick/event split: we wait for either an input event or the next
+    /// tick, whichever comes first, so the graphs keep scrolling even when the user isn't
+    /// pressing any keys.
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+        let mut last_tick = Instant::now();
         while !self.exit {
             terminal.draw(|frame| self.render(frame))?;
-            self.handle_crossterm_events()?;
+
+            let timeout = self.config.tick_rate.saturating_sub(last_tick.elapsed());
+            if event::poll(timeout)? {
+                self.handle_crossterm_events()?;
+            }
+            if last_tick.elapsed() >= self.config.tick_rate {
+                self.on_tick();
+                last_tick = Instant::now();
+            }
         }
+        restore();
         Ok(())
     }
 
+    /// Advances the simulation by one tick, pushing a new sample onto each metric's history and
+    /// discarding the oldest sample once the history exceeds [`HISTORY_LEN`].
+    fn on_tick(&mut self) {
+        push_sample(&mut self.cpu_history, self.cpu_source.sample());
+        push_sample(&mut self.gpu_history, self.gpu_source.sample());
+        push_sample(&mut self.disk_history, self.disk_source.sample());
+        push_sample(&mut self.memory_history, self.memory_source.sample());
+    }
+
     /// Renders the user interface.
     fn render(&mut self, frame: &mut Frame) {
         let bg_block = Block::new().bg(SLATE.c800);
@@ -55,25 +450,63 @@ impl App {
             .spacing(2)
             .areas(top);
 
-        render_graph("CPU", frame, left);
-        render_graph("GPU", frame, right);
+        self.cpu_area = render_graph("CPU", &self.cpu_history, &self.config, frame, left);
+        self.gpu_area = render_graph("GPU", &self.gpu_history, &self.config, frame, right);
 
         let [left, right] = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(2)])
             .spacing(2)
             .areas(mid);
-        render_disk(frame, left);
-        render_memory(frame, right);
+        self.disk_area = render_disk(&self.disk_history, &self.config, frame, left);
+        self.memory_area = render_memory(&self.memory_history, &self.config, frame, right);
+
+        if let Some((panel, index)) = self.hovered {
+            self.render_hover_overlay(frame, panel, index);
+        }
+    }
+
+    /// Draws a small floating label near the cursor showing the hovered panel's name and, when
+    /// the panel has retained data, the value of the sample under the cursor.
+    fn render_hover_overlay(&self, frame: &mut Frame, panel: PanelId, index: usize) {
+        let (area, history) = match panel {
+            PanelId::Cpu => (self.cpu_area, &self.cpu_history),
+            PanelId::Gpu => (self.gpu_area, &self.gpu_history),
+            PanelId::Disk => (self.disk_area, &self.disk_history),
+            PanelId::Memory => (self.memory_area, &self.memory_history),
+        };
+        if area.width == 0 {
+            return;
+        }
+
+        let samples = recent_samples(history, area.width as usize * samples_per_column(&self.config));
+        let value = samples.get(index).copied();
+        let text = match value {
+            Some(value) => format!(" {} {value:.2} ", panel.label()),
+            None => format!(" {} ", panel.label()),
+        };
+
+        let width = (text.chars().count() as u16).min(area.width);
+        // Inverse of the column-to-index scaling in `panel_at`, so the label lands at the cursor
+        // even while the history is still shorter than the panel.
+        let column = (index * area.width as usize / samples.len().max(1)) as u16;
+        let column = column.min(area.width.saturating_sub(1));
+        let x = area
+            .x
+            .saturating_add(column)
+            .min(area.right().saturating_sub(width));
+        // Float the overlay one row above the panel; if the panel already starts at the top of
+        // the frame there's no row above it, so fall back to overlapping the panel's own top row.
+        let y = if area.y == 0 { area.y } else { area.y - 1 };
+
+        let overlay = Line::raw(text).fg(SLATE.c900).bg(SLATE.c100);
+        frame.render_widget(overlay, Rect::new(x, y, width, 1));
     }
 
     /// Reads the crossterm events and updates the state of [`App`].
-    ///
-    /// If your application needs to perform work in between handling events, you can use the
-    /// [`event::poll`] function to check if there are any events available with a timeout.
     fn handle_crossterm_events(&mut self) -> Result<()> {
         match event::read()? {
             // it's important to check KeyEventKind::Press to avoid handling key release events
             Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
-            Event::Mouse(_) => {}
+            Event::Mouse(mouse) => self.on_mouse_event(mouse),
             Event::Resize(_, _) => {}
             _ => {}
         }
@@ -90,13 +523,94 @@ impl App {
         }
     }
 
+    /// Handles mouse-move and click events, updating [`Self::hovered`] to the panel and sample
+    /// index under the cursor so `render` can draw a tooltip next to it.
+    fn on_mouse_event(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Moved | MouseEventKind::Down(_) => {
+                self.hovered = self.panel_at(mouse.column, mouse.row);
+            }
+            _ => {}
+        }
+    }
+
+    /// Maps a terminal position to the panel it falls within and the retained-history sample
+    /// index closest to that column.
+    fn panel_at(&self, column: u16, row: u16) -> Option<(PanelId, usize)> {
+        let position = Position::new(column, row);
+        let panels = [
+            (PanelId::Cpu, self.cpu_area, self.cpu_history.len()),
+            (PanelId::Gpu, self.gpu_area, self.gpu_history.len()),
+            (PanelId::Disk, self.disk_area, self.disk_history.len()),
+            (PanelId::Memory, self.memory_area, self.memory_history.len()),
+        ];
+        for (panel, area, history_len) in panels {
+            if !area.contains(position) {
+                continue;
+            }
+            let len = history_len.min(area.width as usize * samples_per_column(&self.config));
+            if len == 0 {
+                return Some((panel, 0));
+            }
+            let offset = column.saturating_sub(area.x) as usize;
+            let index = (offset * len / area.width.max(1) as usize).min(len - 1);
+            return Some((panel, index));
+        }
+        None
+    }
+
     /// Set running to false to quit the application.
     fn quit(&mut self) {
         self.exit = true;
     }
 }
 
-fn render_graph(name: &str, frame: &mut Frame, area: Rect) {
+/// Pushes `sample` onto the back of `history`, popping from the front once it grows past
+/// [`HISTORY_LEN`] so the buffer behaves like a sliding window.
+fn push_sample(history: &mut VecDeque<f64>, sample: f64) {
+    history.push_back(sample);
+    while history.len() > HISTORY_LEN {
+        history.pop_front();
+    }
+}
+
+/// Returns the most recent `len` samples from `history`, oldest first, ready to hand to a
+/// [`BarGraph`].
+fn recent_samples(history: &VecDeque<f64>, len: usize) -> Vec<f64> {
+    let skip = history.len().saturating_sub(len);
+    history.iter().skip(skip).copied().collect()
+}
+
+/// How many samples a [`BarGraph`] consumes per terminal column for the configured bar style:
+/// braille bars pack two samples per column, solid bars one.
+fn samples_per_column(config: &Config) -> usize {
+    if config.enhanced_graphics {
+        2
+    } else {
+        1
+    }
+}
+
+/// Builds a [`BarGraph`] for `data` using the configured gradient and bar style, shared by every
+/// panel so the gradient/enhanced-graphics wiring only needs to happen once.
+fn bar_graph(data: Vec<f64>, config: &Config) -> BarGraph {
+    let bar_style = if config.enhanced_graphics {
+        BarStyle::Braille
+    } else {
+        BarStyle::Solid
+    };
+    BarGraph::new(data)
+        .with_gradient(config.gradient.resolve())
+        .with_bar_style(bar_style)
+}
+
+fn render_graph(
+    name: &str,
+    history: &VecDeque<f64>,
+    config: &Config,
+    frame: &mut Frame,
+    area: Rect,
+) -> Rect {
     let block = Block::new()
         .borders(Borders::TOP)
         .border_set(symbols::border::FULL)
@@ -106,14 +620,12 @@ fn render_graph(name: &str, frame: &mut Frame, area: Rect) {
     frame.render_widget(&block, area);
 
     let inner = block.inner(area);
-    let data = (0..inner.width * 2)
-        .map(|_| rand::random::<f64>())
-        .collect::<Vec<f64>>();
-    let graph = BarGraph::new(data).with_gradient(colorgrad::preset::plasma());
-    frame.render_widget(graph, inner);
+    let data = recent_samples(history, inner.width as usize * samples_per_column(config));
+    frame.render_widget(bar_graph(data, config), inner);
+    inner
 }
 
-fn render_disk(frame: &mut Frame, area: Rect) {
+fn render_disk(history: &VecDeque<f64>, config: &Config, frame: &mut Frame, area: Rect) -> Rect {
     let block = Block::new()
         .borders(Borders::TOP)
         .border_set(symbols::border::FULL)
@@ -121,9 +633,14 @@ fn render_disk(frame: &mut Frame, area: Rect) {
         .border_style(SLATE.c300)
         .bg(SLATE.c900);
     frame.render_widget(&block, area);
+
+    let inner = block.inner(area);
+    let data = recent_samples(history, inner.width as usize * samples_per_column(config));
+    frame.render_widget(bar_graph(data, config), inner);
+    inner
 }
 
-fn render_memory(frame: &mut Frame, area: Rect) {
+fn render_memory(history: &VecDeque<f64>, config: &Config, frame: &mut Frame, area: Rect) -> Rect {
     let block = Block::new()
         .borders(Borders::TOP)
         .border_set(symbols::border::FULL)
@@ -133,9 +650,7 @@ fn render_memory(frame: &mut Frame, area: Rect) {
     frame.render_widget(&block, area);
 
     let inner = block.inner(area);
-    let data = (0..inner.width * 2)
-        .map(|_| rand::random::<f64>())
-        .collect::<Vec<f64>>();
-    let graph = BarGraph::new(data).with_gradient(colorgrad::preset::blues());
-    frame.render_widget(graph, inner);
+    let data = recent_samples(history, inner.width as usize * samples_per_column(config));
+    frame.render_widget(bar_graph(data, config), inner);
+    inner
 }